@@ -99,10 +99,52 @@ impl<S: Read> JsonlStream<S> {
         }
     }
 
+    /// Reads a single [`Incoming`] message and dispatches it to the matching callback.
+    ///
+    /// This is a convenience for full-duplex peers that can receive a request, a
+    /// notification, or a response on the same connection: the message is classified
+    /// and handed to `on_request`, `on_notification`, or `on_response` accordingly.
+    /// A [`RequestObject`] without an `id` is treated as a notification.
+    ///
+    /// As with [`JsonlStream::read_value()`], this method may return
+    /// [`ErrorKind::WouldBlock`] error if the inner stream is in non-blocking mode.
+    pub fn dispatch_incoming<FReq, FNotify, FResp>(
+        &mut self,
+        mut on_request: FReq,
+        mut on_notification: FNotify,
+        mut on_response: FResp,
+    ) -> Result<(), serde_json::Error>
+    where
+        FReq: FnMut(crate::RequestObject),
+        FNotify: FnMut(crate::RequestObject),
+        FResp: FnMut(crate::ResponseObject),
+    {
+        match self.read_value::<crate::Incoming>()? {
+            crate::Incoming::Request(request) if request.is_notification() => {
+                on_notification(request)
+            }
+            crate::Incoming::Request(request) => on_request(request),
+            crate::Incoming::Response(response) => on_response(response),
+        }
+        Ok(())
+    }
+
     /// Returns the incomplete JSON line in the read buffer.
     pub fn read_buf(&self) -> &[u8] {
         &self.read_buf[self.read_buf_offset..self.read_buf_end]
     }
+
+    /// Discards any buffered read and write data, resetting the framing state.
+    ///
+    /// This is useful before retrying a request, so a partially read or written frame
+    /// from a previous attempt cannot corrupt the next one. Note that it only clears the
+    /// in-memory buffers; it does not reconnect or otherwise touch the inner stream.
+    pub fn reset_buffers(&mut self) {
+        self.read_buf_end = 0;
+        self.read_buf_offset = 0;
+        self.write_buf.clear();
+        self.write_buf_offset = 0;
+    }
 }
 
 impl<S: Write> JsonlStream<S> {
@@ -151,3 +193,349 @@ impl<S: Write> JsonlStream<S> {
         &self.write_buf[self.write_buf_offset..]
     }
 }
+
+/// A stream that frames JSON values with an LSP/DAP-style `Content-Length` header.
+///
+/// Each message is emitted as `Content-Length: <N>\r\n\r\n` followed by exactly `N`
+/// bytes of JSON. This is an alternative to the newline framing used by
+/// [`JsonlStream`], and is required to interoperate with tools speaking the
+/// [base protocol] used by the Language Server and Debug Adapter protocols, or
+/// whenever a serialized value may itself contain a raw newline.
+///
+/// [base protocol]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#baseProtocol
+#[derive(Debug)]
+pub struct FramedStream<S> {
+    inner: S,
+    read_buf: Vec<u8>,
+    read_buf_end: usize,
+    read_buf_offset: usize,
+    write_buf: Vec<u8>,
+    write_buf_offset: usize,
+}
+
+impl<S> FramedStream<S> {
+    /// Makes a new [`FramedStream`] instance.
+    pub fn new(inner: S) -> FramedStream<S> {
+        FramedStream {
+            inner,
+            read_buf: vec![0; 1024],
+            read_buf_end: 0,
+            read_buf_offset: 0,
+            write_buf: Vec::new(),
+            write_buf_offset: 0,
+        }
+    }
+
+    /// Returns a reference to the inner stream.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner stream.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consumes the [`FramedStream`] and returns the inner stream.
+    ///
+    /// Note that any remaining data in the read and write buffers will be lost.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Read> FramedStream<S> {
+    /// Reads a `Content-Length`-framed value from the stream.
+    ///
+    /// The header block is parsed line by line until an empty line; only the
+    /// `Content-Length` header is interpreted and any other headers are ignored.
+    ///
+    /// Note that if the inner stream is in non-blocking mode, this method may return
+    /// [`ErrorKind::WouldBlock`] error.
+    /// If it happens, you should retry this method after the stream becomes readable.
+    pub fn read_value<T>(&mut self) -> Result<T, serde_json::Error>
+    where
+        T: for<'a> Deserialize<'a>,
+    {
+        const HEADER_END: &[u8] = b"\r\n\r\n";
+
+        self.compact_read_buf();
+
+        // Read until the full header block is available.
+        let header_end = loop {
+            if let Some(i) = find_subslice(self.filled(), HEADER_END) {
+                break self.read_buf_offset + i;
+            }
+            self.fill_more()?;
+        };
+
+        let content_length = parse_content_length(&self.read_buf[self.read_buf_offset..header_end])?;
+        let body_start = header_end + HEADER_END.len();
+        let body_end = body_start + content_length;
+
+        // Read until the full body is available.
+        while self.read_buf_end < body_end {
+            self.fill_more()?;
+        }
+
+        let item = serde_json::from_slice(&self.read_buf[body_start..body_end])?;
+        self.read_buf_offset = body_end;
+        Ok(item)
+    }
+
+    fn filled(&self) -> &[u8] {
+        &self.read_buf[self.read_buf_offset..self.read_buf_end]
+    }
+
+    fn compact_read_buf(&mut self) {
+        if self.read_buf_offset != 0 {
+            self.read_buf
+                .copy_within(self.read_buf_offset..self.read_buf_end, 0);
+            self.read_buf_end -= self.read_buf_offset;
+            self.read_buf_offset = 0;
+        }
+    }
+
+    fn fill_more(&mut self) -> Result<(), serde_json::Error> {
+        if self.read_buf_end == self.read_buf.len() {
+            self.read_buf.resize(self.read_buf.len() * 2, 0);
+        }
+
+        let read_size = self
+            .inner
+            .read(&mut self.read_buf[self.read_buf_end..])
+            .map_err(serde_json::Error::io)?;
+        if read_size == 0 {
+            return Err(serde_json::Error::io(ErrorKind::UnexpectedEof.into()));
+        }
+        self.read_buf_end += read_size;
+        Ok(())
+    }
+
+    /// Returns the unconsumed data in the read buffer.
+    pub fn read_buf(&self) -> &[u8] {
+        &self.read_buf[self.read_buf_offset..self.read_buf_end]
+    }
+}
+
+impl<S: Write> FramedStream<S> {
+    /// Writes a `Content-Length`-framed value to the stream.
+    ///
+    /// The value is serialized to a scratch buffer, the header is prepended, and the
+    /// result is flushed.
+    ///
+    /// Note that if the inner stream is in non-blocking mode, this method may return
+    /// [`ErrorKind::WouldBlock`] error.
+    /// If it happens, you should retry by calling [`FramedStream::flush()`] after the stream becomes writable.
+    pub fn write_value<T>(&mut self, value: &T) -> Result<(), serde_json::Error>
+    where
+        T: Serialize,
+    {
+        let body = serde_json::to_vec(value)?;
+        self.write_buf
+            .extend_from_slice(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes());
+        self.write_buf.extend_from_slice(&body);
+        self.flush()?;
+        Ok(())
+    }
+
+    /// Writes all remaining data in the write buffer to the stream.
+    ///
+    /// As with [`FramedStream::write_value()`], this method may return [`ErrorKind::WouldBlock`] error
+    /// if the inner stream is in non-blocking mode.
+    pub fn flush(&mut self) -> Result<(), serde_json::Error> {
+        while self.write_buf_offset < self.write_buf.len() {
+            let written_size = self
+                .inner
+                .write(&self.write_buf[self.write_buf_offset..])
+                .map_err(serde_json::Error::io)?;
+            if written_size == 0 {
+                return Err(serde_json::Error::io(ErrorKind::WriteZero.into()));
+            }
+            self.write_buf_offset += written_size;
+        }
+
+        self.write_buf.clear();
+        self.write_buf_offset = 0;
+        Ok(())
+    }
+
+    /// Returns the remaining data in the write buffer.
+    pub fn write_buf(&self) -> &[u8] {
+        &self.write_buf[self.write_buf_offset..]
+    }
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn parse_content_length(header: &[u8]) -> Result<usize, serde_json::Error> {
+    for line in header.split(|&b| b == b'\n') {
+        let line = std::str::from_utf8(line)
+            .map_err(|_| serde_json::Error::io(ErrorKind::InvalidData.into()))?
+            .trim();
+        if let Some(value) = line
+            .split_once(':')
+            .filter(|(name, _)| name.trim().eq_ignore_ascii_case("Content-Length"))
+            .map(|(_, value)| value.trim())
+        {
+            return value
+                .parse::<usize>()
+                .map_err(|_| serde_json::Error::io(ErrorKind::InvalidData.into()));
+        }
+    }
+    Err(serde_json::Error::io(ErrorKind::InvalidData.into()))
+}
+
+/// Async version of [`JsonlStream`] built on [`tokio`]'s async I/O traits.
+///
+/// Unlike [`JsonlStream`], whose methods return [`ErrorKind::WouldBlock`] when the
+/// inner stream is not ready, this type `.await`s readiness instead, so it can be
+/// driven directly from a tokio runtime.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct AsyncJsonlStream<S> {
+    inner: S,
+    read_buf: Vec<u8>,
+    read_buf_end: usize,
+    read_buf_offset: usize,
+    write_buf: Vec<u8>,
+    write_buf_offset: usize,
+}
+
+#[cfg(feature = "tokio")]
+impl<S> AsyncJsonlStream<S> {
+    /// Makes a new [`AsyncJsonlStream`] instance.
+    pub fn new(inner: S) -> AsyncJsonlStream<S> {
+        AsyncJsonlStream {
+            inner,
+            read_buf: vec![0; 1024],
+            read_buf_end: 0,
+            read_buf_offset: 0,
+            write_buf: Vec::new(),
+            write_buf_offset: 0,
+        }
+    }
+
+    /// Returns a reference to the inner stream.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner stream.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consumes the [`AsyncJsonlStream`] and returns the inner stream.
+    ///
+    /// Note that any remaining data in the read and write buffers will be lost.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Returns the incomplete JSON line in the read buffer.
+    pub fn read_buf(&self) -> &[u8] {
+        &self.read_buf[self.read_buf_offset..self.read_buf_end]
+    }
+
+    /// Returns the remaining data in the write buffer.
+    pub fn write_buf(&self) -> &[u8] {
+        &self.write_buf[self.write_buf_offset..]
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S: tokio::io::AsyncRead + Unpin> AsyncJsonlStream<S> {
+    /// Reads a JSONL value from the stream, awaiting until a full line is available.
+    pub async fn read_value<T>(&mut self) -> Result<T, serde_json::Error>
+    where
+        T: for<'a> Deserialize<'a>,
+    {
+        use tokio::io::AsyncReadExt;
+
+        if self.read_buf_offset != 0 {
+            if let Some(i) = self.read_buf[self.read_buf_offset..self.read_buf_end]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|i| self.read_buf_offset + i)
+            {
+                let item = serde_json::from_slice(&self.read_buf[self.read_buf_offset..i])?;
+                self.read_buf_offset = i + 1;
+                return Ok(item);
+            }
+
+            self.read_buf
+                .copy_within(self.read_buf_offset..self.read_buf_end, 0);
+            self.read_buf_end -= self.read_buf_offset;
+            self.read_buf_offset = 0;
+        }
+
+        loop {
+            if self.read_buf_end == self.read_buf.len() {
+                self.read_buf.resize(self.read_buf.len() * 2, 0);
+            }
+
+            let read_size = self
+                .inner
+                .read(&mut self.read_buf[self.read_buf_end..])
+                .await
+                .map_err(serde_json::Error::io)?;
+            if read_size == 0 {
+                return Err(serde_json::Error::io(ErrorKind::UnexpectedEof.into()));
+            }
+
+            let old_end = self.read_buf_end;
+            self.read_buf_end += read_size;
+
+            if let Some(i) = self.read_buf[old_end..self.read_buf_end]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|i| old_end + i)
+            {
+                let item = serde_json::from_slice(&self.read_buf[..i])?;
+                self.read_buf_offset = i + 1;
+                return Ok(item);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S: tokio::io::AsyncWrite + Unpin> AsyncJsonlStream<S> {
+    /// Writes a JSONL value to the stream, awaiting until all bytes are flushed.
+    pub async fn write_value<T>(&mut self, value: &T) -> Result<(), serde_json::Error>
+    where
+        T: Serialize,
+    {
+        serde_json::to_writer(&mut self.write_buf, value)?;
+        self.write_buf.push(b'\n');
+        self.flush().await?;
+
+        Ok(())
+    }
+
+    /// Writes all remaining data in the write buffer to the stream.
+    pub async fn flush(&mut self) -> Result<(), serde_json::Error> {
+        use tokio::io::AsyncWriteExt;
+
+        while self.write_buf_offset < self.write_buf.len() {
+            let written_size = self
+                .inner
+                .write(&self.write_buf[self.write_buf_offset..])
+                .await
+                .map_err(serde_json::Error::io)?;
+            if written_size == 0 {
+                return Err(serde_json::Error::io(ErrorKind::WriteZero.into()));
+            }
+            self.write_buf_offset += written_size;
+        }
+
+        self.inner.flush().await.map_err(serde_json::Error::io)?;
+        self.write_buf.clear();
+        self.write_buf_offset = 0;
+
+        Ok(())
+    }
+}
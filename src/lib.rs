@@ -58,11 +58,17 @@ mod io;
 mod rpc;
 mod types;
 
-pub use io::JsonlStream;
-pub use rpc::RpcClient;
+#[cfg(feature = "tokio")]
+pub use io::AsyncJsonlStream;
+pub use io::{FramedStream, JsonlStream};
+#[cfg(feature = "tokio")]
+pub use rpc::{AsyncRpcClient, AsyncSubscription};
+pub use rpc::{
+    BatchItem, MappedBatchResponses, RequestIdKind, RetryPolicy, RpcClient, Subscription,
+};
 pub use types::{
-    ErrorCode, ErrorObject, JsonRpcVersion, MaybeBatch, RequestId, RequestObject, RequestParams,
-    ResponseObject,
+    ErrorCode, ErrorObject, Incoming, JsonRpcVersion, MaybeBatch, RequestId, RequestObject,
+    RequestParams, ResponseObject,
 };
 
 #[cfg(test)]
@@ -213,4 +219,176 @@ mod tests {
 
         addr
     }
+
+    #[test]
+    fn test_out_of_order_correlation() {
+        let server_addr = spawn_reorder_server_thread();
+        let socket = TcpStream::connect(server_addr).expect("failed to connect to server");
+        let mut client = RpcClient::new(socket);
+
+        // Pipeline two requests before reading any response.
+        let request1 = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            id: Some(RequestId::Number(1)),
+            method: "foo".to_string(),
+            params: None,
+        };
+        let request2 = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            id: Some(RequestId::Number(2)),
+            method: "bar".to_string(),
+            params: None,
+        };
+        client.send_request(&request1).expect("failed to send request1");
+        client.send_request(&request2).expect("failed to send request2");
+
+        // The server replies out of order and interleaves a notification; correlation
+        // must still hand back the right response for each id.
+        let ResponseObject::Ok { result, id, .. } = client
+            .recv_response(&RequestId::Number(1))
+            .expect("failed to receive response1")
+        else {
+            panic!("expected ok response")
+        };
+        assert_eq!(id, RequestId::Number(1));
+        assert_eq!(result, serde_json::Value::String("foo".to_string()));
+
+        let ResponseObject::Ok { id, .. } = client
+            .recv_response(&RequestId::Number(2))
+            .expect("failed to receive response2")
+        else {
+            panic!("expected ok response")
+        };
+        assert_eq!(id, RequestId::Number(2));
+
+        // The interleaved server notification was buffered, not dropped.
+        let notification = client
+            .poll_notification()
+            .expect("expected a buffered notification");
+        assert_eq!(notification.method, "tick");
+        assert!(notification.is_notification());
+    }
+
+    #[test]
+    fn test_subscription() {
+        let server_addr = spawn_subscription_server_thread();
+        let socket = TcpStream::connect(server_addr).expect("failed to connect to server");
+        let mut client = RpcClient::new(socket);
+
+        let mut subscription = client
+            .subscribe::<_, i64>("foo_subscribe", serde_json::Value::Null, "foo_unsubscribe")
+            .expect("failed to subscribe");
+        assert_eq!(subscription.id(), &serde_json::json!("sub-1"));
+
+        for expected in 0..3 {
+            let item = subscription
+                .next()
+                .expect("subscription ended early")
+                .expect("failed to read subscription item");
+            assert_eq!(item, expected);
+        }
+
+        subscription.unsubscribe().expect("failed to unsubscribe");
+    }
+
+    fn spawn_reorder_server_thread() -> SocketAddr {
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind to address");
+        let addr = listener.local_addr().expect("failed to get local address");
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.expect("failed to accept incoming connection");
+                let mut stream = JsonlStream::new(stream);
+                std::thread::spawn(move || {
+                    let request1: RequestObject =
+                        stream.read_value().expect("failed to read request1");
+                    let request2: RequestObject =
+                        stream.read_value().expect("failed to read request2");
+
+                    // Interleave a server-initiated notification ahead of the responses.
+                    let notification = RequestObject {
+                        jsonrpc: JsonRpcVersion::V2,
+                        id: None,
+                        method: "tick".to_string(),
+                        params: None,
+                    };
+                    stream
+                        .write_value(&notification)
+                        .expect("failed to write notification");
+
+                    // Respond in reverse order.
+                    for request in [request2, request1] {
+                        let response = ResponseObject::Ok {
+                            jsonrpc: JsonRpcVersion::V2,
+                            id: request.id.expect("expected request id"),
+                            result: serde_json::Value::String(request.method),
+                        };
+                        stream
+                            .write_value(&response)
+                            .expect("failed to write response");
+                    }
+                });
+            }
+        });
+
+        addr
+    }
+
+    fn spawn_subscription_server_thread() -> SocketAddr {
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind to address");
+        let addr = listener.local_addr().expect("failed to get local address");
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.expect("failed to accept incoming connection");
+                let mut stream = JsonlStream::new(stream);
+                std::thread::spawn(move || {
+                    // Acknowledge the subscription request with the assigned id.
+                    let request: RequestObject =
+                        stream.read_value().expect("failed to read subscribe request");
+                    let response = ResponseObject::Ok {
+                        jsonrpc: JsonRpcVersion::V2,
+                        id: request.id.expect("expected request id"),
+                        result: serde_json::json!("sub-1"),
+                    };
+                    stream
+                        .write_value(&response)
+                        .expect("failed to write subscribe response");
+
+                    // Push a few notifications for this subscription.
+                    for i in 0..3 {
+                        let mut params = serde_json::Map::new();
+                        params.insert("subscription".to_string(), serde_json::json!("sub-1"));
+                        params.insert("result".to_string(), serde_json::json!(i));
+                        let notification = RequestObject {
+                            jsonrpc: JsonRpcVersion::V2,
+                            id: None,
+                            method: "foo_update".to_string(),
+                            params: Some(RequestParams::Object(params)),
+                        };
+                        stream
+                            .write_value(&notification)
+                            .expect("failed to write notification");
+                    }
+
+                    // Acknowledge the teardown.
+                    let request: RequestObject = stream
+                        .read_value()
+                        .expect("failed to read unsubscribe request");
+                    let response = ResponseObject::Ok {
+                        jsonrpc: JsonRpcVersion::V2,
+                        id: request.id.expect("expected request id"),
+                        result: serde_json::Value::Bool(true),
+                    };
+                    stream
+                        .write_value(&response)
+                        .expect("failed to write unsubscribe response");
+                });
+            }
+        });
+
+        addr
+    }
 }
@@ -1,13 +1,151 @@
+use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
+use std::marker::PhantomData;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use crate::JsonlStream;
+use crate::{
+    ErrorCode, ErrorObject, JsonRpcVersion, JsonlStream, RequestId, RequestObject, RequestParams,
+    ResponseObject,
+};
+
+/// Serializes `params` into the [`RequestParams`] envelope representation.
+///
+/// `null` params (including `()`) become `None`, matching a request with no `params`
+/// member. Any value that is neither an array, an object, nor `null` is rejected, as
+/// JSON-RPC 2.0 only allows structured parameters.
+fn into_params<P: Serialize>(params: P) -> Result<Option<RequestParams>, serde_json::Error> {
+    match serde_json::to_value(params)? {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Array(values) => Ok(Some(RequestParams::Array(values))),
+        serde_json::Value::Object(map) => Ok(Some(RequestParams::Object(map))),
+        _ => Err(serde_json::Error::io(std::io::ErrorKind::InvalidInput.into())),
+    }
+}
+
+/// Wraps a [`serde_json::Error`] as an [`ErrorObject`], guessing the code.
+fn error_object(error: serde_json::Error) -> ErrorObject {
+    ErrorObject {
+        code: ErrorCode::guess(&error),
+        message: error.to_string(),
+        data: None,
+    }
+}
+
+/// Representation used for the request IDs auto-generated by [`RpcClient::call_method`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestIdKind {
+    /// Plain numeric IDs, e.g. `0`, `1`, `2`.
+    Number,
+
+    /// Numeric IDs rendered as strings with the given prefix, e.g. `"req-0"`, `"req-1"`.
+    StringPrefixed(String),
+}
+
+impl Default for RequestIdKind {
+    fn default() -> Self {
+        Self::Number
+    }
+}
+
+/// Policy controlling how [`RpcClient::call_with_retry`] re-sends a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: usize,
+
+    /// Delay to wait between attempts.
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Makes a new [`RetryPolicy`] with the given number of attempts and no backoff.
+    pub const fn new(max_attempts: usize) -> Self {
+        Self {
+            max_attempts,
+            backoff: Duration::ZERO,
+        }
+    }
+
+    /// Sets the delay to wait between attempts.
+    pub const fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Responses to a batch request, correlated with the issued requests by [`RequestId`].
+///
+/// JSON-RPC 2.0 permits a server to return batch responses in any order, so results
+/// are keyed by id rather than by arrival order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappedBatchResponses {
+    /// Responses keyed by the request ID they correspond to.
+    pub responses: HashMap<RequestId, ResponseObject>,
+
+    /// Error responses whose `id` is `None` (the server could not parse the request).
+    ///
+    /// These cannot be correlated with a specific request and are surfaced here
+    /// instead of being dropped.
+    pub unattributed: Vec<ResponseObject>,
+}
+
+/// An entry in a [`RpcClient::batch_request`] call.
+///
+/// A [`BatchItem::Request`] is assigned an ID and produces a slot in the result vector;
+/// a [`BatchItem::Notification`] carries no ID, expects no response, and produces no slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchItem<P> {
+    /// A request that expects a response.
+    Request {
+        /// Method name.
+        method: String,
+        /// Request parameters.
+        params: P,
+    },
+
+    /// A notification that expects no response.
+    Notification {
+        /// Method name.
+        method: String,
+        /// Notification parameters.
+        params: P,
+    },
+}
+
+impl<P> BatchItem<P> {
+    /// Makes a [`BatchItem::Request`] entry.
+    pub fn request(method: &str, params: P) -> Self {
+        Self::Request {
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    /// Makes a [`BatchItem::Notification`] entry.
+    pub fn notification(method: &str, params: P) -> Self {
+        Self::Notification {
+            method: method.to_string(),
+            params,
+        }
+    }
+}
 
 /// JSON-RPC client.
 #[derive(Debug)]
 pub struct RpcClient<S> {
     stream: JsonlStream<S>,
+    next_id: i64,
+    id_kind: RequestIdKind,
+    stashed_responses: Vec<ResponseObject>,
+    notifications: VecDeque<RequestObject>,
 }
 
 impl<S: Read + Write> RpcClient<S> {
@@ -15,9 +153,55 @@ impl<S: Read + Write> RpcClient<S> {
     pub fn new(stream: S) -> Self {
         Self {
             stream: JsonlStream::new(stream),
+            next_id: 0,
+            id_kind: RequestIdKind::default(),
+            stashed_responses: Vec::new(),
+            notifications: VecDeque::new(),
+        }
+    }
+
+    /// Sets the [`RequestIdKind`] used by [`RpcClient::call_method`] when allocating IDs.
+    pub fn set_id_kind(&mut self, id_kind: RequestIdKind) {
+        self.id_kind = id_kind;
+    }
+
+    /// Allocates a fresh monotonic request ID in the configured [`RequestIdKind`].
+    fn next_request_id(&mut self) -> RequestId {
+        let n = self.next_id;
+        self.next_id += 1;
+        match &self.id_kind {
+            RequestIdKind::Number => RequestId::Number(n),
+            RequestIdKind::StringPrefixed(prefix) => RequestId::String(format!("{prefix}{n}")),
         }
     }
 
+    /// Builds a request with an auto-generated ID, sends it, and returns the response.
+    ///
+    /// This is a convenience over [`RpcClient::call`] for callers that do not want to
+    /// track request IDs themselves: a fresh monotonic ID (in the configured
+    /// [`RequestIdKind`], see [`RpcClient::set_id_kind`]) is allocated, the
+    /// `"jsonrpc": "2.0"` envelope is filled in, and the returned response is verified
+    /// to echo the issued ID.
+    pub fn call_method(
+        &mut self,
+        method: &str,
+        params: Option<RequestParams>,
+    ) -> Result<ResponseObject, serde_json::Error> {
+        let id = self.next_request_id();
+        let request = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method: method.to_string(),
+            params,
+            id: Some(id.clone()),
+        };
+
+        let response: ResponseObject = self.call(&request)?;
+        if response.id() != Some(&id) {
+            return Err(serde_json::Error::io(std::io::ErrorKind::InvalidData.into()));
+        }
+        Ok(response)
+    }
+
     /// RPC call (request).
     ///
     /// The `request` can be a batch (array) if it includes at least one non-notification request object.
@@ -32,6 +216,209 @@ impl<S: Read + Write> RpcClient<S> {
         Ok(response)
     }
 
+    /// Sends a batch request and correlates the responses by [`RequestId`].
+    ///
+    /// Unlike [`RpcClient::call`], which assumes the server returns batch responses in
+    /// the same order as the requests, this method collects the responses into a map
+    /// keyed by id, so out-of-order replies are handled correctly. Notifications (request
+    /// objects without an `id`) produce no response and are therefore absent from the map.
+    ///
+    /// Error responses whose `id` is `None` cannot be attributed to a request and are
+    /// returned in [`MappedBatchResponses::unattributed`].
+    ///
+    /// If `requests` contains only notifications the server returns nothing, so this
+    /// method returns an empty [`MappedBatchResponses`] without reading.
+    pub fn call_batch_mapped(
+        &mut self,
+        requests: &[RequestObject],
+    ) -> Result<MappedBatchResponses, serde_json::Error> {
+        self.stream.write_value(&requests)?;
+
+        let mut mapped = MappedBatchResponses {
+            responses: HashMap::new(),
+            unattributed: Vec::new(),
+        };
+        if requests.iter().all(|request| request.id.is_none()) {
+            return Ok(mapped);
+        }
+
+        let responses: Vec<ResponseObject> = self.stream.read_value()?;
+        for response in responses {
+            match response.id() {
+                Some(id) => {
+                    mapped.responses.insert(id.clone(), response);
+                }
+                None => mapped.unattributed.push(response),
+            }
+        }
+        Ok(mapped)
+    }
+
+    /// Typed request helper that fills in the JSON-RPC 2.0 envelope.
+    ///
+    /// A fresh monotonic ID (see [`RpcClient::set_id_kind`]) is allocated, `params` is
+    /// serialized into the request, and the success `result` is deserialized into `R`.
+    /// An error response is returned as `Err(ErrorObject)`; transport and
+    /// (de)serialization failures are mapped to an [`ErrorObject`] via
+    /// [`ErrorCode::guess`]. The response ID is verified to echo the one sent.
+    pub fn request<P, R>(&mut self, method: &str, params: P) -> Result<R, ErrorObject>
+    where
+        P: Serialize,
+        R: for<'de> Deserialize<'de>,
+    {
+        let id = self.next_request_id();
+        let request = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method: method.to_string(),
+            params: into_params(params).map_err(error_object)?,
+            id: Some(id.clone()),
+        };
+
+        let response: ResponseObject = self.call(&request).map_err(error_object)?;
+        match response {
+            ResponseObject::Ok { result, id: got, .. } => {
+                if got != id {
+                    return Err(error_object(serde_json::Error::io(
+                        std::io::ErrorKind::InvalidData.into(),
+                    )));
+                }
+                serde_json::from_value(result).map_err(error_object)
+            }
+            ResponseObject::Err { error, .. } => Err(error),
+        }
+    }
+
+    /// Typed notification helper that fills in the JSON-RPC 2.0 envelope.
+    ///
+    /// Notifications carry no `id` and expect no response.
+    pub fn notification<P>(&mut self, method: &str, params: P) -> Result<(), serde_json::Error>
+    where
+        P: Serialize,
+    {
+        let notification = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method: method.to_string(),
+            params: into_params(params)?,
+            id: None,
+        };
+        self.cast(&notification)
+    }
+
+    /// Typed batch helper that maps each result back to its request.
+    ///
+    /// A distinct auto-generated ID is assigned to every [`BatchItem::Request`], the
+    /// batch array is written, and the (possibly reordered) response array is read back
+    /// and re-sorted to match the input order by matching IDs. Each slot carries either
+    /// the deserialized success value or the server's [`ErrorObject`]; the returned
+    /// vector is positional, in request order. [`BatchItem::Notification`] entries expect
+    /// no response and therefore produce no slot in the output.
+    pub fn batch_request<P, R>(
+        &mut self,
+        items: Vec<BatchItem<P>>,
+    ) -> Result<Vec<Result<R, ErrorObject>>, serde_json::Error>
+    where
+        P: Serialize,
+        R: for<'de> Deserialize<'de>,
+    {
+        let mut requests = Vec::with_capacity(items.len());
+        let mut ids = Vec::new();
+        for item in items {
+            match item {
+                BatchItem::Request { method, params } => {
+                    let id = self.next_request_id();
+                    requests.push(RequestObject {
+                        jsonrpc: JsonRpcVersion::V2,
+                        method,
+                        params: into_params(params)?,
+                        id: Some(id.clone()),
+                    });
+                    ids.push(id);
+                }
+                BatchItem::Notification { method, params } => {
+                    requests.push(RequestObject {
+                        jsonrpc: JsonRpcVersion::V2,
+                        method,
+                        params: into_params(params)?,
+                        id: None,
+                    });
+                }
+            }
+        }
+
+        self.stream.write_value(&requests)?;
+        if ids.is_empty() {
+            // An all-notification batch yields no response per JSON-RPC 2.0.
+            return Ok(Vec::new());
+        }
+        let responses: Vec<ResponseObject> = self.stream.read_value()?;
+
+        let mut by_id: HashMap<RequestId, ResponseObject> = responses
+            .into_iter()
+            .filter_map(|r| r.id().cloned().map(|id| (id, r)))
+            .collect();
+
+        let results = ids
+            .iter()
+            .map(|id| match by_id.remove(id) {
+                Some(response) => match response.into_std_result() {
+                    Ok(value) => serde_json::from_value(value).map_err(error_object),
+                    Err(error) => Err(error),
+                },
+                None => Err(error_object(serde_json::Error::io(
+                    std::io::ErrorKind::UnexpectedEof.into(),
+                ))),
+            })
+            .collect();
+        Ok(results)
+    }
+
+    /// Sends a request, retrying on transient failures according to `policy`.
+    ///
+    /// A retry is attempted when [`RpcClient::call`] fails with a transport/IO error
+    /// (other than [`ErrorKind::WouldBlock`](std::io::ErrorKind::WouldBlock), which means
+    /// the non-blocking stream is merely not ready and should be awaited, not re-sent),
+    /// or when the server returns an error response whose code is retriable (see
+    /// [`ErrorObject::is_retriable`](crate::ErrorObject::is_retriable)). Client-fault
+    /// responses are returned immediately without retrying.
+    ///
+    /// The retry re-sends over the *same* underlying stream; the framing buffers are
+    /// reset between attempts so a partially read frame does not corrupt the next try.
+    /// Reconnecting a genuinely broken transport is the caller's responsibility.
+    pub fn call_with_retry<REQ>(
+        &mut self,
+        request: &REQ,
+        policy: &RetryPolicy,
+    ) -> Result<ResponseObject, serde_json::Error>
+    where
+        REQ: Serialize,
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let last_attempt = attempt >= policy.max_attempts;
+
+            match self.call::<REQ, ResponseObject>(request) {
+                Ok(ResponseObject::Err { error, .. }) if error.is_retriable() && !last_attempt => {}
+                Err(e)
+                    if !last_attempt && e.classify() == serde_json::error::Category::Io =>
+                {
+                    // `WouldBlock` is not a failure to retry: the stream is just not
+                    // ready. Surface it so the caller can await readiness.
+                    let io = std::io::Error::from(e);
+                    if io.kind() == std::io::ErrorKind::WouldBlock {
+                        return Err(serde_json::Error::io(io));
+                    }
+                }
+                other => return other,
+            }
+
+            self.stream.reset_buffers();
+            if !policy.backoff.is_zero() {
+                std::thread::sleep(policy.backoff);
+            }
+        }
+    }
+
     /// RPC call (notification).
     ///
     /// The `notification` can be a batch (array).
@@ -43,6 +430,98 @@ impl<S: Read + Write> RpcClient<S> {
         Ok(())
     }
 
+    /// Writes a request without waiting for its response.
+    ///
+    /// This lets callers pipeline several requests before reading any responses; each
+    /// reply is later collected by [`RpcClient::recv_response`], which correlates by id.
+    pub fn send_request(&mut self, request: &RequestObject) -> Result<(), serde_json::Error> {
+        self.stream.write_value(request)?;
+        Ok(())
+    }
+
+    /// Reads frames until the response for `id` arrives, returning it.
+    ///
+    /// Responses for other outstanding IDs and server-initiated requests/notifications
+    /// that arrive first are stashed in side buffers rather than discarded: a
+    /// non-matching response can still be retrieved by a later `recv_response`, and a
+    /// notification by [`RpcClient::poll_notification`]. This makes `call`-style usage
+    /// robust against out-of-order or interleaved traffic.
+    ///
+    /// An error response with no `id` (the server could not parse our request) cannot be
+    /// correlated with any pending call and is returned to the current waiter rather than
+    /// being stashed where nothing could ever retrieve it.
+    pub fn recv_response(
+        &mut self,
+        id: &RequestId,
+    ) -> Result<ResponseObject, serde_json::Error> {
+        if let Some(i) = self
+            .stashed_responses
+            .iter()
+            .position(|r| r.id() == Some(id))
+        {
+            return Ok(self.stashed_responses.remove(i));
+        }
+
+        loop {
+            match self.stream.read_value::<crate::Incoming>()? {
+                crate::Incoming::Request(request) => self.notifications.push_back(request),
+                crate::Incoming::Response(response) => match response.id() {
+                    Some(got) if got == id => return Ok(response),
+                    Some(_) => self.stashed_responses.push(response),
+                    None => return Ok(response),
+                },
+            }
+        }
+    }
+
+    /// Returns the next buffered server-initiated request or notification, if any.
+    ///
+    /// Frames are buffered here as a side effect of [`RpcClient::recv_response`] reading
+    /// past them while awaiting a particular response.
+    pub fn poll_notification(&mut self) -> Option<RequestObject> {
+        self.notifications.pop_front()
+    }
+
+    /// Subscribes to a server-push stream and returns a [`Subscription`] handle.
+    ///
+    /// The subscription request is sent, the initial response is read to obtain the
+    /// server-assigned subscription id, and the returned handle yields each subsequent
+    /// server notification whose `params.subscription` equals that id. Unrelated frames
+    /// encountered while iterating are buffered on the client (see
+    /// [`RpcClient::poll_notification`] and [`RpcClient::recv_response`]).
+    ///
+    /// `unsubscribe_method` is the method used by [`Subscription::unsubscribe`] to tear
+    /// the subscription down.
+    pub fn subscribe<P, T>(
+        &mut self,
+        subscribe_method: &str,
+        params: P,
+        unsubscribe_method: &str,
+    ) -> Result<Subscription<'_, S, T>, serde_json::Error>
+    where
+        P: Serialize,
+    {
+        let id = self.next_request_id();
+        let request = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method: subscribe_method.to_string(),
+            params: into_params(params)?,
+            id: Some(id.clone()),
+        };
+        self.stream.write_value(&request)?;
+        let response = self.recv_response(&id)?;
+        let sub_id = response.into_std_result().map_err(|_| {
+            serde_json::Error::io(std::io::ErrorKind::ConnectionRefused.into())
+        })?;
+
+        Ok(Subscription {
+            client: self,
+            sub_id,
+            unsubscribe_method: unsubscribe_method.to_string(),
+            _item: PhantomData,
+        })
+    }
+
     /// Returns a reference to the underlying JSONL stream.
     pub fn stream(&mut self) -> &JsonlStream<S> {
         &self.stream
@@ -58,3 +537,348 @@ impl<S: Read + Write> RpcClient<S> {
         self.stream
     }
 }
+
+/// Classifies an error as a closed stream (`None`) or one to propagate (`Some`).
+///
+/// A subscription ends cleanly when the peer closes the connection, so an EOF or
+/// connection-reset error terminates iteration instead of being yielded as an error.
+fn closed_stream_error(error: serde_json::Error) -> Option<serde_json::Error> {
+    if error.classify() == serde_json::error::Category::Io {
+        let io = std::io::Error::from(error);
+        if matches!(
+            io.kind(),
+            std::io::ErrorKind::UnexpectedEof
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+        ) {
+            return None;
+        }
+        return Some(serde_json::Error::io(io));
+    }
+    Some(error)
+}
+
+/// Extracts the `(subscription, result)` pair from a server-push notification.
+///
+/// Returns `None` if the request is not a notification carrying by-name parameters with
+/// a `subscription` member.
+fn subscription_payload(
+    request: &RequestObject,
+) -> Option<(&serde_json::Value, serde_json::Value)> {
+    if !request.is_notification() {
+        return None;
+    }
+    let Some(RequestParams::Object(map)) = &request.params else {
+        return None;
+    };
+    let subscription = map.get("subscription")?;
+    let result = map
+        .get("result")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    Some((subscription, result))
+}
+
+/// A handle to a long-lived server-push subscription.
+///
+/// Iterating yields each notification belonging to this subscription, deserialized into
+/// `T`. Frames that belong to other subscriptions, other requests, or other responses
+/// are buffered back onto the originating [`RpcClient`] rather than being dropped.
+#[derive(Debug)]
+pub struct Subscription<'a, S, T> {
+    client: &'a mut RpcClient<S>,
+    sub_id: serde_json::Value,
+    unsubscribe_method: String,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<S: Read + Write, T> Subscription<'_, S, T> {
+    /// Returns the server-assigned subscription id.
+    pub fn id(&self) -> &serde_json::Value {
+        &self.sub_id
+    }
+
+    /// Reads the next notification belonging to this subscription.
+    fn recv_next(&mut self) -> Result<T, serde_json::Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        loop {
+            match self.client.stream.read_value::<crate::Incoming>()? {
+                crate::Incoming::Request(request) => {
+                    if let Some((subscription, result)) = subscription_payload(&request) {
+                        if subscription == &self.sub_id {
+                            return serde_json::from_value(result);
+                        }
+                    }
+                    self.client.notifications.push_back(request);
+                }
+                crate::Incoming::Response(response) => {
+                    self.client.stashed_responses.push(response);
+                }
+            }
+        }
+    }
+
+    /// Tears the subscription down and drops any of its still-buffered notifications.
+    ///
+    /// The `unsubscribe_method` supplied to [`RpcClient::subscribe`] is invoked with the
+    /// subscription id as its sole by-position parameter.
+    pub fn unsubscribe(self) -> Result<ResponseObject, serde_json::Error> {
+        let sub_id = self.sub_id;
+        let method = self.unsubscribe_method;
+        let client = self.client;
+
+        let id = client.next_request_id();
+        let request = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method,
+            params: Some(RequestParams::Array(vec![sub_id.clone()])),
+            id: Some(id.clone()),
+        };
+        let response = client
+            .stream
+            .write_value(&request)
+            .and_then(|()| client.recv_response(&id));
+
+        client
+            .notifications
+            .retain(|request| match subscription_payload(request) {
+                Some((subscription, _)) => subscription != &sub_id,
+                None => true,
+            });
+        response
+    }
+}
+
+impl<S: Read + Write, T> Iterator for Subscription<'_, S, T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    type Item = Result<T, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.recv_next() {
+            Ok(item) => Some(Ok(item)),
+            Err(e) => closed_stream_error(e).map(Err),
+        }
+    }
+}
+
+/// Async counterpart of [`RpcClient`], built on [`tokio`]'s async I/O traits.
+///
+/// It mirrors [`RpcClient::call`] and [`RpcClient::cast`] as `async fn`s backed by an
+/// [`AsyncJsonlStream`](crate::AsyncJsonlStream), so many concurrent RPC connections can
+/// be driven on a single runtime without a thread per socket.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct AsyncRpcClient<S> {
+    stream: crate::AsyncJsonlStream<S>,
+    next_id: i64,
+    id_kind: RequestIdKind,
+    stashed_responses: Vec<ResponseObject>,
+    notifications: VecDeque<RequestObject>,
+}
+
+#[cfg(feature = "tokio")]
+impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> AsyncRpcClient<S> {
+    /// Makes a new [`AsyncRpcClient`] instance.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: crate::AsyncJsonlStream::new(stream),
+            next_id: 0,
+            id_kind: RequestIdKind::default(),
+            stashed_responses: Vec::new(),
+            notifications: VecDeque::new(),
+        }
+    }
+
+    /// Sets the [`RequestIdKind`] used when auto-allocating subscription request IDs.
+    pub fn set_id_kind(&mut self, id_kind: RequestIdKind) {
+        self.id_kind = id_kind;
+    }
+
+    fn next_request_id(&mut self) -> RequestId {
+        let n = self.next_id;
+        self.next_id += 1;
+        match &self.id_kind {
+            RequestIdKind::Number => RequestId::Number(n),
+            RequestIdKind::StringPrefixed(prefix) => RequestId::String(format!("{prefix}{n}")),
+        }
+    }
+
+    /// Reads frames until the response for `id` arrives, buffering unrelated frames.
+    pub async fn recv_response(
+        &mut self,
+        id: &RequestId,
+    ) -> Result<ResponseObject, serde_json::Error> {
+        if let Some(i) = self
+            .stashed_responses
+            .iter()
+            .position(|r| r.id() == Some(id))
+        {
+            return Ok(self.stashed_responses.remove(i));
+        }
+        loop {
+            match self.stream.read_value::<crate::Incoming>().await? {
+                crate::Incoming::Request(request) => self.notifications.push_back(request),
+                crate::Incoming::Response(response) => match response.id() {
+                    Some(got) if got == id => return Ok(response),
+                    Some(_) => self.stashed_responses.push(response),
+                    None => return Ok(response),
+                },
+            }
+        }
+    }
+
+    /// Returns the next buffered server-initiated request or notification, if any.
+    pub fn poll_notification(&mut self) -> Option<RequestObject> {
+        self.notifications.pop_front()
+    }
+
+    /// Subscribes to a server-push stream and returns an [`AsyncSubscription`] handle.
+    ///
+    /// This mirrors [`RpcClient::subscribe`] for the async client: await
+    /// [`AsyncSubscription::next`] to receive each subsequent notification.
+    pub async fn subscribe<P, T>(
+        &mut self,
+        subscribe_method: &str,
+        params: P,
+        unsubscribe_method: &str,
+    ) -> Result<AsyncSubscription<'_, S, T>, serde_json::Error>
+    where
+        P: Serialize,
+    {
+        let id = self.next_request_id();
+        let request = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method: subscribe_method.to_string(),
+            params: into_params(params)?,
+            id: Some(id.clone()),
+        };
+        self.stream.write_value(&request).await?;
+        let response = self.recv_response(&id).await?;
+        let sub_id = response.into_std_result().map_err(|_| {
+            serde_json::Error::io(std::io::ErrorKind::ConnectionRefused.into())
+        })?;
+
+        Ok(AsyncSubscription {
+            client: self,
+            sub_id,
+            unsubscribe_method: unsubscribe_method.to_string(),
+            _item: PhantomData,
+        })
+    }
+
+    /// RPC call (request).
+    ///
+    /// The `request` can be a batch (array) if it includes at least one non-notification request object.
+    /// For a batch request that contains only notifications, use [`AsyncRpcClient::cast`] instead.
+    pub async fn call<REQ, RES>(&mut self, request: &REQ) -> Result<RES, serde_json::Error>
+    where
+        REQ: Serialize,
+        RES: for<'de> Deserialize<'de>,
+    {
+        self.stream.write_value(request).await?;
+        let response = self.stream.read_value().await?;
+        Ok(response)
+    }
+
+    /// RPC call (notification).
+    ///
+    /// The `notification` can be a batch (array).
+    pub async fn cast<T>(&mut self, notification: &T) -> Result<(), serde_json::Error>
+    where
+        T: Serialize,
+    {
+        self.stream.write_value(notification).await?;
+        Ok(())
+    }
+
+    /// Returns a reference to the underlying JSONL stream.
+    pub fn stream(&self) -> &crate::AsyncJsonlStream<S> {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the underlying JSONL stream.
+    pub fn stream_mut(&mut self) -> &mut crate::AsyncJsonlStream<S> {
+        &mut self.stream
+    }
+
+    /// Consumes the [`AsyncRpcClient`] and returns the underlying JSONL stream.
+    pub fn into_stream(self) -> crate::AsyncJsonlStream<S> {
+        self.stream
+    }
+}
+
+/// Async counterpart of [`Subscription`], yielded by [`AsyncRpcClient::subscribe`].
+///
+/// Await [`AsyncSubscription::next`] to obtain the next notification belonging to this
+/// subscription; it plays the role of an async `Stream` without pulling in an extra
+/// dependency.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct AsyncSubscription<'a, S, T> {
+    client: &'a mut AsyncRpcClient<S>,
+    sub_id: serde_json::Value,
+    unsubscribe_method: String,
+    _item: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin, T> AsyncSubscription<'_, S, T> {
+    /// Returns the server-assigned subscription id.
+    pub fn id(&self) -> &serde_json::Value {
+        &self.sub_id
+    }
+
+    /// Awaits the next notification belonging to this subscription.
+    pub async fn next(&mut self) -> Result<T, serde_json::Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        loop {
+            match self.client.stream.read_value::<crate::Incoming>().await? {
+                crate::Incoming::Request(request) => {
+                    if let Some((subscription, result)) = subscription_payload(&request) {
+                        if subscription == &self.sub_id {
+                            return serde_json::from_value(result);
+                        }
+                    }
+                    self.client.notifications.push_back(request);
+                }
+                crate::Incoming::Response(response) => {
+                    self.client.stashed_responses.push(response);
+                }
+            }
+        }
+    }
+
+    /// Tears the subscription down and drops any of its still-buffered notifications.
+    pub async fn unsubscribe(self) -> Result<ResponseObject, serde_json::Error> {
+        let sub_id = self.sub_id;
+        let method = self.unsubscribe_method;
+        let client = self.client;
+
+        let id = client.next_request_id();
+        let request = RequestObject {
+            jsonrpc: JsonRpcVersion::V2,
+            method,
+            params: Some(RequestParams::Array(vec![sub_id.clone()])),
+            id: Some(id.clone()),
+        };
+        let response = match client.stream.write_value(&request).await {
+            Ok(()) => client.recv_response(&id).await,
+            Err(e) => Err(e),
+        };
+
+        client
+            .notifications
+            .retain(|request| match subscription_payload(request) {
+                Some((subscription, _)) => subscription != &sub_id,
+                None => true,
+            });
+        response
+    }
+}
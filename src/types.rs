@@ -116,6 +116,13 @@ pub struct RequestObject {
     pub id: Option<RequestId>,
 }
 
+impl RequestObject {
+    /// Returns `true` if this request object is a notification (i.e. it has no `id`).
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+}
+
 impl FromStr for RequestObject {
     type Err = serde_json::Error;
 
@@ -268,6 +275,36 @@ impl Display for ResponseObject {
     }
 }
 
+/// A message received by a peer on a duplex JSONL connection.
+///
+/// A full-duplex JSON-RPC peer can receive a request, a notification, or a response on
+/// the same stream and must decide which it is. This untagged enum performs that
+/// discrimination on deserialization; use [`RequestObject::is_notification`] to further
+/// tell a request apart from a notification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Incoming {
+    /// A request or notification object.
+    Request(RequestObject),
+
+    /// A response object.
+    Response(ResponseObject),
+}
+
+impl FromStr for Incoming {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl Display for Incoming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        serde_json::to_string(self).expect("unreachable").fmt(f)
+    }
+}
+
 /// Error object.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ErrorObject {
@@ -282,6 +319,18 @@ pub struct ErrorObject {
     pub data: Option<serde_json::Value>,
 }
 
+impl ErrorObject {
+    /// Returns `true` if the failure this error represents is worth retrying.
+    ///
+    /// Server errors (the reserved `-32099..=-32000` range, see
+    /// [`ErrorCode::is_server_error`]) are treated as transient and therefore
+    /// retriable, whereas client-fault codes such as [`ErrorCode::INVALID_REQUEST`],
+    /// [`ErrorCode::METHOD_NOT_FOUND`], and [`ErrorCode::INVALID_PARAMS`] are terminal.
+    pub const fn is_retriable(&self) -> bool {
+        self.code.is_server_error()
+    }
+}
+
 impl FromStr for ErrorObject {
     type Err = serde_json::Error;
 